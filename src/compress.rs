@@ -0,0 +1,278 @@
+//! Decompression codecs for the payload carried inside a `/vcs` blob.
+//!
+//! Kyma has shipped a few different encodings of the blob over the years
+//! (raw headerless deflate, a legacy `'?'`-prefixed variant, and plain
+//! uncompressed data), and callers over a text transport may also hand us
+//! zlib- or gzip-wrapped deflate. [`decompress_auto`] inspects the leading
+//! bytes of a blob and picks the matching [`Decompressor`] so callers don't
+//! have to guess which container they received.
+
+use deflate::{deflate_bytes, deflate_bytes_gzip, deflate_bytes_zlib};
+use inflate::{inflate_bytes, inflate_bytes_zlib};
+use std::fmt;
+
+/// Which codec was used to decompress a blob.
+///
+/// Returned alongside the decompressed bytes so callers can log or assert
+/// on the path that was actually taken instead of relying on stderr output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Codec {
+    /// Headerless ("raw") DEFLATE, Kyma's default optimised encoding.
+    RawDeflate,
+    /// Zlib-wrapped DEFLATE (2-byte header + Adler-32 trailer).
+    Zlib,
+    /// Gzip-wrapped DEFLATE (`\x1f\x8b` header + CRC-32 trailer).
+    Gzip,
+    /// Snappy-framed payload.
+    #[cfg(feature = "snappy")]
+    Snappy,
+    /// Not compressed; the blob is already EventID/value pairs.
+    None,
+}
+
+/// A decompression failure, tagged with the codec that was attempted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecompressError {
+    RawDeflate(String),
+    Zlib(String),
+    Gzip(String),
+    #[cfg(feature = "snappy")]
+    Snappy(String),
+}
+
+impl fmt::Display for DecompressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecompressError::RawDeflate(e) => write!(f, "raw deflate decompression failed: {e}"),
+            DecompressError::Zlib(e) => write!(f, "zlib decompression failed: {e}"),
+            DecompressError::Gzip(e) => write!(f, "gzip decompression failed: {e}"),
+            #[cfg(feature = "snappy")]
+            DecompressError::Snappy(e) => write!(f, "snappy decompression failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for DecompressError {}
+
+/// A single decompression strategy for a blob's payload.
+pub trait Decompressor {
+    /// The codec this decompressor implements.
+    fn codec(&self) -> Codec;
+
+    /// Decompress `input`, returning the raw EventID/value bytes.
+    fn decompress(&self, input: &[u8]) -> Result<Vec<u8>, DecompressError>;
+}
+
+/// Headerless DEFLATE, as emitted by Kyma when "Optimise Kyma Control
+/// Communication" compresses a VCS blob.
+pub struct RawDeflate;
+
+impl Decompressor for RawDeflate {
+    fn codec(&self) -> Codec {
+        Codec::RawDeflate
+    }
+
+    fn decompress(&self, input: &[u8]) -> Result<Vec<u8>, DecompressError> {
+        inflate_bytes(input).map_err(DecompressError::RawDeflate)
+    }
+}
+
+/// Zlib-wrapped DEFLATE (RFC 1950).
+pub struct Zlib;
+
+impl Decompressor for Zlib {
+    fn codec(&self) -> Codec {
+        Codec::Zlib
+    }
+
+    fn decompress(&self, input: &[u8]) -> Result<Vec<u8>, DecompressError> {
+        inflate_bytes_zlib(input).map_err(DecompressError::Zlib)
+    }
+}
+
+/// Gzip-wrapped DEFLATE (RFC 1952). Only the fixed 10-byte header plus the
+/// optional extra/name/comment fields are parsed; the CRC-32/ISIZE trailer
+/// is not verified.
+pub struct Gzip;
+
+impl Decompressor for Gzip {
+    fn codec(&self) -> Codec {
+        Codec::Gzip
+    }
+
+    fn decompress(&self, input: &[u8]) -> Result<Vec<u8>, DecompressError> {
+        const HEADER_LEN: usize = 10;
+        if input.len() < HEADER_LEN || input[0] != 0x1f || input[1] != 0x8b {
+            return Err(DecompressError::Gzip("missing gzip header".to_string()));
+        }
+        let flags = input[3];
+        let mut offset = HEADER_LEN;
+
+        if flags & 0x04 != 0 {
+            // FEXTRA: 2-byte little-endian length prefix.
+            let len = input
+                .get(offset..offset + 2)
+                .map(|b| u16::from_le_bytes([b[0], b[1]]) as usize)
+                .ok_or_else(|| DecompressError::Gzip("truncated FEXTRA length".to_string()))?;
+            offset += 2 + len;
+        }
+        if flags & 0x08 != 0 {
+            // FNAME: null-terminated.
+            offset += input
+                .get(offset..)
+                .and_then(|b| b.iter().position(|&b| b == 0))
+                .ok_or_else(|| DecompressError::Gzip("unterminated FNAME".to_string()))?
+                + 1;
+        }
+        if flags & 0x10 != 0 {
+            // FCOMMENT: null-terminated.
+            offset += input
+                .get(offset..)
+                .and_then(|b| b.iter().position(|&b| b == 0))
+                .ok_or_else(|| DecompressError::Gzip("unterminated FCOMMENT".to_string()))?
+                + 1;
+        }
+        if flags & 0x02 != 0 {
+            // FHCRC: 2-byte header CRC16, not verified.
+            offset += 2;
+        }
+
+        let body = input
+            .get(offset..)
+            .ok_or_else(|| DecompressError::Gzip("truncated after header".to_string()))?;
+        inflate_bytes(body).map_err(DecompressError::Gzip)
+    }
+}
+
+/// Snappy-framed payload. Only compiled in with the `snappy` feature.
+#[cfg(feature = "snappy")]
+pub struct Snappy;
+
+#[cfg(feature = "snappy")]
+impl Decompressor for Snappy {
+    fn codec(&self) -> Codec {
+        Codec::Snappy
+    }
+
+    fn decompress(&self, input: &[u8]) -> Result<Vec<u8>, DecompressError> {
+        snap::raw::Decoder::new()
+            .decompress_vec(input)
+            .map_err(|e| DecompressError::Snappy(e.to_string()))
+    }
+}
+
+/// Strips the literal `'?'` byte that Kyma's legacy format put in front of
+/// raw deflate data. [`decompress_auto`] calls this when a blob starts
+/// with `'?'`.
+fn strip_legacy_prefix(blob: &[u8]) -> &[u8] {
+    &blob[1..]
+}
+
+/// Compression to apply when encoding a blob with [`crate::to_blob`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Compression {
+    /// Don't compress; write the EventID/value pairs as-is.
+    #[default]
+    None,
+    /// Headerless DEFLATE, matching Kyma's own optimised encoding.
+    RawDeflate,
+    /// Zlib-wrapped DEFLATE.
+    Zlib,
+    /// Gzip-wrapped DEFLATE.
+    Gzip,
+}
+
+/// Compress `data` according to `compression`, for building a blob with
+/// [`crate::to_blob`].
+pub fn compress(data: &[u8], compression: Compression) -> Vec<u8> {
+    match compression {
+        Compression::None => data.to_vec(),
+        Compression::RawDeflate => deflate_bytes(data),
+        Compression::Zlib => deflate_bytes_zlib(data),
+        Compression::Gzip => deflate_bytes_gzip(data),
+    }
+}
+
+/// Auto-detect the codec used on `blob` and decompress it.
+///
+/// Detection order:
+/// 1. Gzip, if the blob starts with the `\x1f\x8b` magic.
+/// 2. Zlib, if the first two bytes form a valid zlib header (a multiple of
+///    31 with a DEFLATE compression method in the low nibble).
+/// 3. The legacy `'?'`-prefixed raw deflate format.
+/// 4. Raw deflate.
+/// 5. Uncompressed, if raw deflate fails to parse.
+pub fn decompress_auto(blob: &[u8]) -> Result<(Vec<u8>, Codec), DecompressError> {
+    if blob.len() >= 2 && blob[0] == 0x1f && blob[1] == 0x8b {
+        return Gzip.decompress(blob).map(|d| (d, Codec::Gzip));
+    }
+
+    if blob.len() >= 2 {
+        let header = (blob[0] as u16) << 8 | blob[1] as u16;
+        if header.is_multiple_of(31) && blob[0] & 0x0f == 8 {
+            return Zlib.decompress(blob).map(|d| (d, Codec::Zlib));
+        }
+    }
+
+    if blob.first() == Some(&b'?') {
+        return RawDeflate
+            .decompress(strip_legacy_prefix(blob))
+            .map(|d| (d, Codec::RawDeflate));
+    }
+
+    match RawDeflate.decompress(blob) {
+        Ok(decompressed) => Ok((decompressed, Codec::RawDeflate)),
+        Err(_) => Ok((blob.to_vec(), Codec::None)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decompresses_legacy_question_mark_prefixed_raw_deflate() {
+        let payload = b"legacy blob payload".to_vec();
+        let mut blob = vec![b'?'];
+        blob.extend_from_slice(&deflate_bytes(&payload));
+
+        let (data, codec) = decompress_auto(&blob).unwrap();
+        assert_eq!(data, payload);
+        assert_eq!(codec, Codec::RawDeflate);
+    }
+
+    #[test]
+    fn decompresses_gzip_with_fextra_and_fname_fields() {
+        let payload = b"hello kyma".to_vec();
+        let deflated = deflate_bytes(&payload);
+
+        let mut blob = vec![0x1f, 0x8b, 8, 0x04 | 0x08]; // magic, CM=deflate, FLG=FEXTRA|FNAME
+        blob.extend_from_slice(&[0, 0, 0, 0]); // MTIME
+        blob.push(0); // XFL
+        blob.push(0xff); // OS: unknown
+
+        let extra = b"xx";
+        blob.extend_from_slice(&(extra.len() as u16).to_le_bytes());
+        blob.extend_from_slice(extra);
+
+        blob.extend_from_slice(b"payload.bin\0"); // FNAME
+
+        blob.extend_from_slice(&deflated);
+        blob.extend_from_slice(&[0; 8]); // CRC-32 + ISIZE trailer, not verified
+
+        let (data, codec) = decompress_auto(&blob).unwrap();
+        assert_eq!(data, payload);
+        assert_eq!(codec, Codec::Gzip);
+    }
+
+    #[cfg(feature = "snappy")]
+    #[test]
+    fn snappy_decompressor_round_trips_a_compressed_payload() {
+        let payload = b"snappy payload round trip".to_vec();
+        let compressed = snap::raw::Encoder::new().compress_vec(&payload).unwrap();
+
+        let data = Snappy.decompress(&compressed).unwrap();
+        assert_eq!(data, payload);
+        assert_eq!(Snappy.codec(), Codec::Snappy);
+    }
+}
@@ -0,0 +1,162 @@
+//! Streaming decoder for `/vcs` messages delivered over a framed or
+//! fragmented transport (TCP, a length-framed pipe, ...) where a single
+//! read may return a partial message or several messages back to back.
+
+use crate::compress;
+use crate::error::DecodeError;
+use crate::osc::{OscArg, OscMessage, OscParseError};
+use crate::KymaConcreteEvent;
+use bytes::{Buf, BytesMut};
+
+/// Incrementally decodes `/vcs` messages out of a growing [`BytesMut`].
+///
+/// Call [`KymaDecoder::decode`] each time more bytes are appended to the
+/// buffer. It returns `Ok(Some(events))` and advances `buf` past exactly
+/// the message it consumed as soon as one is complete, `Ok(None)` (leaving
+/// `buf` untouched) if more data is needed, and `Err` if the buffered bytes
+/// are already malformed. Bytes after a decoded message — the start of the
+/// next one, or a trailing partial message — are left in place, so no
+/// leftover data is ever copied.
+///
+/// Not every `Err` leaves the decoder able to make progress, though: see
+/// the note on [`KymaDecoder::decode`] about [`DecodeError::Osc`].
+#[derive(Debug, Default)]
+pub struct KymaDecoder {
+    _private: (),
+}
+
+impl KymaDecoder {
+    /// Creates a new, empty decoder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attempts to decode one complete `/vcs` message from the front of `buf`.
+    ///
+    /// A [`DecodeError::BadAddress`], [`DecodeError::UnexpectedArgs`],
+    /// [`DecodeError::MisalignedBlob`] or [`DecodeError::Decompress`] error
+    /// means the framing itself (address/type-tag/blob-length) parsed fine,
+    /// so `buf` is advanced past the offending message before the error is
+    /// returned — the next `decode()` call picks up at whatever follows it.
+    ///
+    /// A [`DecodeError::Osc`] error is different: [`OscMessage::parse`]
+    /// failed before it could determine the message's length (an unknown
+    /// type tag, a missing `,`, invalid UTF-8), so there's no valid boundary
+    /// to advance past and `buf` is left untouched. Calling `decode()` again
+    /// without modifying `buf` will return the identical error forever —
+    /// callers that hit `DecodeError::Osc` must discard or resynchronize the
+    /// buffer themselves (e.g. drop the connection, or scan for the next
+    /// plausible address pattern) rather than retry in a loop.
+    pub fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Vec<KymaConcreteEvent>>, DecodeError> {
+        let (msg, consumed) = match OscMessage::parse(&buf[..]) {
+            Ok(parsed) => parsed,
+            // Not yet an error - just wait for more bytes to arrive.
+            Err(OscParseError::UnterminatedAddress | OscParseError::Truncated { .. }) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        // The message is complete, so advance past it now, before any of the
+        // checks below can return an error — otherwise a malformed-but-complete
+        // message would get reparsed (and re-rejected) forever.
+        buf.advance(consumed);
+
+        if msg.address != "/vcs" {
+            return Err(DecodeError::BadAddress { found: msg.address });
+        }
+        let blob_data = match msg.args.as_slice() {
+            [OscArg::Blob(blob)] => blob,
+            _ => return Err(DecodeError::UnexpectedArgs { address: msg.address }),
+        };
+
+        let events = if blob_data.is_empty() {
+            Vec::new()
+        } else {
+            let (data, _codec) = compress::decompress_auto(blob_data)?;
+            if data.len() % 8 != 0 {
+                return Err(DecodeError::MisalignedBlob { len: data.len() });
+            }
+            data.chunks_exact(8)
+                .map(|chunk| KymaConcreteEvent {
+                    event_id: i32::from_be_bytes(chunk[0..4].try_into().unwrap()),
+                    value: f32::from_be_bytes(chunk[4..8].try_into().unwrap()),
+                })
+                .collect()
+        };
+
+        Ok(Some(events))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{to_blob, Compression};
+
+    #[test]
+    fn waits_for_a_complete_message() {
+        let full = to_blob(&[KymaConcreteEvent { event_id: 42, value: 2.5 }], Compression::None);
+        let mut buf = BytesMut::from(&full[..full.len() - 1]);
+        let mut decoder = KymaDecoder::new();
+
+        assert_eq!(decoder.decode(&mut buf).unwrap(), None);
+
+        buf.extend_from_slice(&full[full.len() - 1..]);
+        let events = decoder.decode(&mut buf).unwrap().unwrap();
+        assert_eq!(events, vec![KymaConcreteEvent { event_id: 42, value: 2.5 }]);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn decodes_two_concatenated_messages_without_copying_the_tail() {
+        let first = to_blob(&[KymaConcreteEvent { event_id: 1, value: 1.0 }], Compression::None);
+        let second = to_blob(&[KymaConcreteEvent { event_id: 2, value: 2.0 }], Compression::None);
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(&first);
+        buf.extend_from_slice(&second);
+
+        let mut decoder = KymaDecoder::new();
+        let first_events = decoder.decode(&mut buf).unwrap().unwrap();
+        assert_eq!(first_events, vec![KymaConcreteEvent { event_id: 1, value: 1.0 }]);
+
+        let second_events = decoder.decode(&mut buf).unwrap().unwrap();
+        assert_eq!(second_events, vec![KymaConcreteEvent { event_id: 2, value: 2.0 }]);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn advances_past_a_malformed_message_so_the_next_good_one_still_decodes() {
+        let bad = OscMessage {
+            address: "/not-vcs".to_string(),
+            args: vec![],
+        }
+        .to_bytes();
+        let good = to_blob(&[KymaConcreteEvent { event_id: 1, value: 1.0 }], Compression::None);
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(&bad);
+        buf.extend_from_slice(&good);
+
+        let mut decoder = KymaDecoder::new();
+        assert!(matches!(decoder.decode(&mut buf), Err(DecodeError::BadAddress { .. })));
+
+        let events = decoder.decode(&mut buf).unwrap().unwrap();
+        assert_eq!(events, vec![KymaConcreteEvent { event_id: 1, value: 1.0 }]);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn osc_parse_errors_leave_buf_untouched_and_are_not_skippable() {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"/x\0\0"); // address
+        buf.extend_from_slice(b",z\0\0"); // unrecognised type tag 'z'
+
+        let mut decoder = KymaDecoder::new();
+        let before = buf.clone();
+
+        assert!(matches!(decoder.decode(&mut buf), Err(DecodeError::Osc(_))));
+        assert_eq!(buf, before);
+
+        // Decoding again without modifying buf returns the identical error
+        // forever, by design: the caller, not the decoder, must resynchronize.
+        assert!(matches!(decoder.decode(&mut buf), Err(DecodeError::Osc(_))));
+        assert_eq!(buf, before);
+    }
+}
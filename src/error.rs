@@ -0,0 +1,85 @@
+//! Structured decode errors.
+//!
+//! Low-level framing problems (truncated buffers, bad type tags, ...) are
+//! reported by the general [`crate::osc::OscParseError`] parser, with the
+//! byte offset where they occur, and wrapped in [`DecodeError::Osc`]; that
+//! is enough position context to tell a truncated UDP datagram apart from a
+//! genuinely malformed packet, mirroring the way `base64`'s decoder
+//! pinpoints the offending offset/byte rather than returning a bare
+//! message. The other variants report what was found (the address, the
+//! decoded blob length, the underlying codec error) but no offset.
+
+use crate::compress::DecompressError;
+use crate::osc::OscParseError;
+use std::fmt;
+
+/// Everything that can go wrong decoding a `/vcs` OSC message.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DecodeError {
+    /// The OSC message was malformed at the wire-format level, so its true
+    /// length is unknown. Unlike the other variants, [`KymaDecoder`](crate::KymaDecoder)
+    /// cannot skip past this and advance its buffer — see
+    /// [`KymaDecoder::decode`](crate::KymaDecoder::decode) for what callers
+    /// need to do instead.
+    Osc(OscParseError),
+    /// The OSC address pattern was present but wasn't `/vcs`.
+    BadAddress { found: String },
+    /// The message's arguments weren't the single blob `/vcs` expects.
+    UnexpectedArgs { address: String },
+    /// The decoded (post-decompression) blob isn't a whole number of
+    /// 8-byte EventID/value pairs.
+    MisalignedBlob { len: usize },
+    /// Decompressing the blob's payload failed.
+    Decompress(DecompressError),
+    /// The text handed to [`crate::from_base64`] wasn't valid base64 (bad
+    /// character or bad length).
+    InvalidBase64(base64::DecodeError),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Osc(e) => write!(f, "{e}"),
+            DecodeError::BadAddress { found } => {
+                write!(f, "unexpected address pattern: {found:?} (expected \"/vcs\")")
+            }
+            DecodeError::UnexpectedArgs { address } => {
+                write!(f, "{address} message did not carry a single blob argument")
+            }
+            DecodeError::MisalignedBlob { len } => {
+                write!(f, "decoded blob length {len} is not a multiple of 8")
+            }
+            DecodeError::Decompress(e) => write!(f, "{e}"),
+            DecodeError::InvalidBase64(e) => write!(f, "invalid base64: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DecodeError::Osc(e) => Some(e),
+            DecodeError::Decompress(e) => Some(e),
+            DecodeError::InvalidBase64(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<OscParseError> for DecodeError {
+    fn from(e: OscParseError) -> Self {
+        DecodeError::Osc(e)
+    }
+}
+
+impl From<DecompressError> for DecodeError {
+    fn from(e: DecompressError) -> Self {
+        DecodeError::Decompress(e)
+    }
+}
+
+impl From<base64::DecodeError> for DecodeError {
+    fn from(e: base64::DecodeError) -> Self {
+        DecodeError::InvalidBase64(e)
+    }
+}
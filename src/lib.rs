@@ -15,9 +15,21 @@ that changed value
 ... repeat EventID and value pairs for each widget that changed value.
  */
 
-use inflate::inflate_bytes;
 use std::fmt;
 
+mod compress;
+mod decoder;
+mod error;
+mod osc;
+mod text;
+pub use compress::{Codec, Compression, DecompressError, Decompressor, Gzip, RawDeflate, Zlib};
+#[cfg(feature = "snappy")]
+pub use compress::Snappy;
+pub use decoder::KymaDecoder;
+pub use error::DecodeError;
+pub use osc::{OscArg, OscMessage, OscParseError};
+pub use text::{from_base64, to_base64, Base64Alphabet};
+
 #[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct KymaConcreteEvent {
     pub event_id: i32,
@@ -54,112 +66,53 @@ impl Default for KymaConcreteEvent {
 
 /// Decodes a Kyma VCS OSC message into a vector of KymaConcreteEvent objects.
 ///
-/// This function is optimized for performance:
-/// - Uses direct byte access instead of slices where possible
-/// - Performs minimal bounds checking
-/// - Pre-allocates result vector to exact size needed
-/// - Handles headerless deflate data with '?' prefix
-/// - No unnecessary allocations or copies
+/// Parses the general OSC envelope via [`OscMessage::parse`], then expects
+/// address `/vcs` with a single blob argument, auto-detecting and undoing
+/// whichever compression container Kyma used for the blob's payload (see
+/// `compress::decompress_auto`).
 ///
 /// # Arguments
 /// * `raw` - The raw OSC message bytes
 ///
 /// # Returns
-/// * `Result<Vec<KymaConcreteEvent>, String>` - The decoded events or an error message
-pub fn from_blob(raw: &[u8]) -> Result<Vec<KymaConcreteEvent>, String> {
-    // Fast path for minimum size check
-    if raw.len() < 12 {
-        return Err("Buffer is too small to contain required fields".to_string());
-    }
-
-    // Read and validate the address pattern
-    let addr_end = match raw.iter().position(|&b| b == 0) {
-        Some(pos) => pos,
-        None => return Err("Address pattern not null-terminated".to_string()),
-    };
-    
-    // Validate address is "/vcs"
-    if addr_end != 4 || &raw[0..4] != b"/vcs" {
-        return Err("Unexpected address pattern".to_string());
-    }
+/// * `Result<Vec<KymaConcreteEvent>, DecodeError>` - The decoded events, or
+///   the reason decoding failed
+pub fn from_blob(raw: &[u8]) -> Result<Vec<KymaConcreteEvent>, DecodeError> {
+    from_blob_with_codec(raw).map(|(events, _codec)| events)
+}
 
-    // Address pattern must be padded to 4 bytes
-    let addr_padded_len = (addr_end + 4) & !3;
+/// Same as [`from_blob`], but also reports which [`Codec`] the blob's
+/// compression was auto-detected as, so callers can log or assert on the
+/// path that was actually taken.
+pub fn from_blob_with_codec(raw: &[u8]) -> Result<(Vec<KymaConcreteEvent>, Codec), DecodeError> {
+    let (msg, _consumed) = OscMessage::parse(raw)?;
 
-    // Fast check for type tag
-    let type_tag_start = addr_padded_len;
-    if raw.len() <= type_tag_start + 1 || raw[type_tag_start] != b',' || raw[type_tag_start + 1] != b'b' {
-        return Err("Invalid type tag, expected `,b`".to_string());
+    if msg.address != "/vcs" {
+        return Err(DecodeError::BadAddress { found: msg.address });
     }
+    let blob_data = match msg.args.as_slice() {
+        [OscArg::Blob(blob)] => blob,
+        _ => return Err(DecodeError::UnexpectedArgs { address: msg.address }),
+    };
 
-    let type_tag_padded_len = type_tag_start + 4; // Type tag must be padded to 4 bytes
-
-    // Read the blob length
-    let blob_length_offset = type_tag_padded_len;
-    if raw.len() < blob_length_offset + 4 {
-        return Err("Buffer too short for blob length".to_string());
-    }
-    
-    let blob_length = u32::from_be_bytes([
-        raw[blob_length_offset], 
-        raw[blob_length_offset + 1], 
-        raw[blob_length_offset + 2], 
-        raw[blob_length_offset + 3]
-    ]) as usize;
-
-    // Read the blob data
-    let blob_start = blob_length_offset + 4;
-    let blob_end = blob_start + blob_length;
-    
-    if raw.len() < blob_end {
-        return Err("Buffer too short for blob data".to_string());
+    if blob_data.is_empty() {
+        return Ok((Vec::new(), Codec::None));
     }
-    
-    let blob_data = &raw[blob_start..blob_end];
-
-
-
-    // Handle Kyma-specific compression on the blob data
-    let data = if !blob_data.is_empty() {
-        // First, try to decompress assuming it's raw deflate data (no '?' prefix)
-        match inflate_bytes(&blob_data) {
-            Ok(decompressed) => {
-                print!("Successfully decompressed raw deflate data");
-                decompressed
-            }
-            Err(e) => {
-                eprintln!("DEFLATE error {:?}", e);
-                // If that fails, check for '?' prefix (legacy format)
-                if blob_data[0] == b'?' {
-                    let deflate_data = &blob_data[1..];
-                    match inflate_bytes(&deflate_data) {
-                        Ok(decompressed) => {
-                            print!("Successfully decompressed '?' prefixed data");
-                            decompressed
-                        }
-                        Err(_) => return Err("Failed to decompress data with '?' prefix".to_string()),
-                    }
-                } else {
-                    // Not compressed at all, use raw data
-                    print!("Using uncompressed data");
-                    blob_data.to_vec()
-                }
-            }
-        }
-    } else {
-        return Err("Empty blob data".to_string());
-    };
 
+    // Auto-detect and undo whichever compression container Kyma used for
+    // the blob (raw deflate, the legacy '?'-prefixed variant, zlib, gzip,
+    // or none at all).
+    let (data, codec) = compress::decompress_auto(blob_data)?;
 
     // Decode the blob data (8 bytes per EventID/value pair)
     if data.len() % 8 != 0 {
-        return Err("Blob length is not a multiple of 8".to_string());
+        return Err(DecodeError::MisalignedBlob { len: data.len() });
     }
 
     // Pre-allocate the exact size needed for results
     let event_count = data.len() / 8;
     let mut results = Vec::with_capacity(event_count);
-    
+
     // Process all chunks in one pass
     for i in (0..data.len()).step_by(8) {
         if i + 8 <= data.len() {
@@ -169,7 +122,27 @@ pub fn from_blob(raw: &[u8]) -> Result<Vec<KymaConcreteEvent>, String> {
         }
     }
 
-    Ok(results)
+    Ok((results, codec))
+}
+
+/// Encodes `events` as a `/vcs` OSC message, the inverse of [`from_blob`].
+///
+/// Builds the EventID/value pairs, compresses them according to
+/// `compression`, and wraps the result as an `OscMessage` with address
+/// `/vcs` and a single blob argument.
+pub fn to_blob(events: &[KymaConcreteEvent], compression: Compression) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(events.len() * 8);
+    for event in events {
+        payload.extend_from_slice(&event.event_id.to_be_bytes());
+        payload.extend_from_slice(&event.value.to_be_bytes());
+    }
+    let blob_data = compress::compress(&payload, compression);
+
+    OscMessage {
+        address: "/vcs".to_string(),
+        args: vec![OscArg::Blob(blob_data)],
+    }
+    .to_bytes()
 }
 
 #[cfg(test)]
@@ -177,6 +150,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::approx_constant)]
     fn test_uncompressed_data() {
         // Create a simple OSC message with /vcs address and blob data
         let mut message = Vec::new();
@@ -204,38 +178,45 @@ mod tests {
     }
     
     #[test]
-    fn test_compressed_data() {
-        // Create a simple OSC message with /vcs address and compressed blob data
-        let mut message = Vec::new();
-        
-        // Address: "/vcs\0"
-        message.extend_from_slice(b"/vcs\0\0\0\0");
-        
-        // Type tag: ",b\0\0"
-        message.extend_from_slice(b",b\0\0");
-        
-        // Create the raw event data
-        let mut event_data = Vec::new();
-        event_data.extend_from_slice(&[0, 0, 0, 123]); // event_id = 123
-        event_data.extend_from_slice(&[0xbf, 0x9d, 0x70, 0xa4]); // value = -1.23
-        
-        // Compress the event data using inflate's test helper
-        // Since we can't easily compress with inflate, we'll use a pre-compressed value
-        // This is a simplified test - in real code we'd need to properly compress
-        let compressed_data = vec![0x73, 0x74, 0x75, 0x62]; // Stub compressed data
-        
-        // Add the '?' prefix for Kyma compressed data
-        let mut blob_data = vec![b'?'];
-        blob_data.extend_from_slice(&compressed_data);
-        
-        // Add blob length
-        message.extend_from_slice(&(blob_data.len() as u32).to_be_bytes());
-        
-        // Add blob data
-        message.extend_from_slice(&blob_data);
-        
-        // This test will fail because we're using stub compressed data
-        // In a real test, we would need proper compressed data
-        // assert!(from_blob(&message).is_ok());
+    fn test_round_trip_uncompressed() {
+        let events = vec![
+            KymaConcreteEvent { event_id: 123, value: -1.23 },
+            KymaConcreteEvent { event_id: 7, value: 0.5 },
+        ];
+        let message = to_blob(&events, Compression::None);
+        assert_eq!(from_blob(&message).unwrap(), events);
+    }
+
+    #[test]
+    fn test_round_trip_raw_deflate() {
+        let events = vec![KymaConcreteEvent { event_id: 42, value: 2.5 }];
+        let message = to_blob(&events, Compression::RawDeflate);
+        let (decoded, codec) = from_blob_with_codec(&message).unwrap();
+        assert_eq!(decoded, events);
+        assert_eq!(codec, Codec::RawDeflate);
+    }
+
+    #[test]
+    fn test_round_trip_zlib() {
+        let events = vec![KymaConcreteEvent { event_id: 42, value: 2.5 }];
+        let message = to_blob(&events, Compression::Zlib);
+        let (decoded, codec) = from_blob_with_codec(&message).unwrap();
+        assert_eq!(decoded, events);
+        assert_eq!(codec, Codec::Zlib);
+    }
+
+    #[test]
+    fn test_round_trip_gzip() {
+        let events = vec![KymaConcreteEvent { event_id: 42, value: 2.5 }];
+        let message = to_blob(&events, Compression::Gzip);
+        let (decoded, codec) = from_blob_with_codec(&message).unwrap();
+        assert_eq!(decoded, events);
+        assert_eq!(codec, Codec::Gzip);
+    }
+
+    #[test]
+    fn test_round_trip_empty() {
+        let message = to_blob(&[], Compression::None);
+        assert_eq!(from_blob(&message).unwrap(), vec![]);
     }
 }
\ No newline at end of file
@@ -0,0 +1,276 @@
+//! General-purpose OSC message parsing.
+//!
+//! This is deliberately independent of the `/vcs`-specific framing used
+//! elsewhere in this crate: it understands the OSC 1.0 wire format (a
+//! null-terminated, 4-byte-padded address pattern, a `,`-prefixed type tag
+//! string, then one argument per tag) for any address and any mix of `i`
+//! (`i32`), `f` (`f32`), `s` (padded string) and `b` (length-prefixed blob)
+//! arguments, so it can be reused for future Kyma messages beyond `/vcs`.
+//! Blob bytes are read and written as exactly their declared length: the
+//! padding to a multiple of 4 that OSC 1.0 places after a blob is neither
+//! written nor skipped.
+
+use std::fmt;
+
+/// A single OSC argument, tagged by its OSC type tag character.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OscArg {
+    /// `i`: a 32-bit big-endian integer.
+    Int(i32),
+    /// `f`: a 32-bit big-endian float.
+    Float(f32),
+    /// `s`: a null-terminated string, padded to 4 bytes.
+    String(String),
+    /// `b`: a `u32`-length-prefixed, big-endian byte blob.
+    Blob(Vec<u8>),
+}
+
+/// A parsed OSC message: an address pattern plus its tagged arguments.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OscMessage {
+    pub address: String,
+    pub args: Vec<OscArg>,
+}
+
+/// Failures parsing the general OSC wire format.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OscParseError {
+    /// The address pattern wasn't null-terminated within the buffer.
+    UnterminatedAddress,
+    /// The address pattern wasn't valid UTF-8.
+    InvalidAddress,
+    /// The byte at `offset`, where the type tag string should start, wasn't `,`.
+    MissingTypeTagComma { offset: usize },
+    /// An unrecognised type tag character at `offset`.
+    UnknownTypeTag { tag: u8, offset: usize },
+    /// The buffer ended before a required field, at `offset`, could be read.
+    Truncated { offset: usize, needed: usize, got: usize },
+    /// A string argument's bytes, starting at `offset`, weren't valid UTF-8.
+    InvalidStringArg { offset: usize },
+}
+
+impl fmt::Display for OscParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OscParseError::UnterminatedAddress => write!(f, "address pattern not null-terminated"),
+            OscParseError::InvalidAddress => write!(f, "address pattern is not valid UTF-8"),
+            OscParseError::MissingTypeTagComma { offset } => {
+                write!(f, "missing type tag ',' at offset {offset}")
+            }
+            OscParseError::UnknownTypeTag { tag, offset } => {
+                write!(f, "unknown type tag {:?} at offset {offset}", *tag as char)
+            }
+            OscParseError::Truncated { offset, needed, got } => write!(
+                f,
+                "truncated at offset {offset}: needed {needed} more bytes, got {got}"
+            ),
+            OscParseError::InvalidStringArg { offset } => {
+                write!(f, "string argument at offset {offset} is not valid UTF-8")
+            }
+        }
+    }
+}
+
+impl std::error::Error for OscParseError {}
+
+/// Returns the smallest multiple of 4 strictly greater than `len`: the
+/// padded size of a `len`-byte string plus its null terminator (so 4 maps
+/// to 8, not 4).
+fn pad4(len: usize) -> usize {
+    (len + 4) & !3
+}
+
+impl OscMessage {
+    /// Parses one OSC message from the front of `raw`.
+    ///
+    /// Returns the parsed message and the number of bytes it consumed, so
+    /// callers can handle multiple concatenated messages.
+    pub fn parse(raw: &[u8]) -> Result<(OscMessage, usize), OscParseError> {
+        let addr_end = raw
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or(OscParseError::UnterminatedAddress)?;
+        let address = std::str::from_utf8(&raw[..addr_end])
+            .map_err(|_| OscParseError::InvalidAddress)?
+            .to_string();
+        let mut offset = pad4(addr_end);
+
+        if raw.len() < offset + 1 {
+            return Err(OscParseError::Truncated {
+                offset,
+                needed: 1,
+                got: raw.len().saturating_sub(offset),
+            });
+        }
+        if raw[offset] != b',' {
+            return Err(OscParseError::MissingTypeTagComma { offset });
+        }
+        let tag_start = offset + 1;
+        let tag_end = raw[tag_start..]
+            .iter()
+            .position(|&b| b == 0)
+            .map(|pos| tag_start + pos)
+            .ok_or(OscParseError::Truncated {
+                offset: tag_start,
+                needed: 1,
+                got: raw.len().saturating_sub(tag_start),
+            })?;
+        let type_tags = raw[tag_start..tag_end].to_vec();
+        offset = pad4(tag_end);
+
+        let mut args = Vec::with_capacity(type_tags.len());
+        for (i, &tag) in type_tags.iter().enumerate() {
+            match tag {
+                b'i' => {
+                    let bytes = raw.get(offset..offset + 4).ok_or(OscParseError::Truncated {
+                        offset,
+                        needed: 4,
+                        got: raw.len().saturating_sub(offset),
+                    })?;
+                    args.push(OscArg::Int(i32::from_be_bytes(bytes.try_into().unwrap())));
+                    offset += 4;
+                }
+                b'f' => {
+                    let bytes = raw.get(offset..offset + 4).ok_or(OscParseError::Truncated {
+                        offset,
+                        needed: 4,
+                        got: raw.len().saturating_sub(offset),
+                    })?;
+                    args.push(OscArg::Float(f32::from_be_bytes(bytes.try_into().unwrap())));
+                    offset += 4;
+                }
+                b's' => {
+                    let tail = raw.get(offset..).ok_or(OscParseError::Truncated {
+                        offset,
+                        needed: 1,
+                        got: 0,
+                    })?;
+                    let str_end = tail
+                        .iter()
+                        .position(|&b| b == 0)
+                        .ok_or(OscParseError::Truncated { offset, needed: 1, got: tail.len() })?;
+                    let s = std::str::from_utf8(&tail[..str_end])
+                        .map_err(|_| OscParseError::InvalidStringArg { offset })?
+                        .to_string();
+                    args.push(OscArg::String(s));
+                    offset += pad4(str_end);
+                }
+                b'b' => {
+                    let len_bytes = raw.get(offset..offset + 4).ok_or(OscParseError::Truncated {
+                        offset,
+                        needed: 4,
+                        got: raw.len().saturating_sub(offset),
+                    })?;
+                    let blob_len = u32::from_be_bytes(len_bytes.try_into().unwrap()) as usize;
+                    let blob_start = offset + 4;
+                    let blob_end = blob_start + blob_len;
+                    let blob = raw
+                        .get(blob_start..blob_end)
+                        .ok_or(OscParseError::Truncated {
+                            offset: blob_start,
+                            needed: blob_len,
+                            got: raw.len().saturating_sub(blob_start),
+                        })?
+                        .to_vec();
+                    args.push(OscArg::Blob(blob));
+                    offset = blob_end;
+                }
+                other => return Err(OscParseError::UnknownTypeTag { tag: other, offset: tag_start + i }),
+            }
+        }
+
+        Ok((OscMessage { address, args }, offset))
+    }
+
+    /// Serialises this message back to its OSC wire representation.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(self.address.as_bytes());
+        pad_with_nulls(&mut out, self.address.len());
+
+        let mut type_tags = vec![b','];
+        for arg in &self.args {
+            type_tags.push(match arg {
+                OscArg::Int(_) => b'i',
+                OscArg::Float(_) => b'f',
+                OscArg::String(_) => b's',
+                OscArg::Blob(_) => b'b',
+            });
+        }
+        out.extend_from_slice(&type_tags);
+        pad_with_nulls(&mut out, type_tags.len());
+
+        for arg in &self.args {
+            match arg {
+                OscArg::Int(v) => out.extend_from_slice(&v.to_be_bytes()),
+                OscArg::Float(v) => out.extend_from_slice(&v.to_be_bytes()),
+                OscArg::String(s) => {
+                    out.extend_from_slice(s.as_bytes());
+                    pad_with_nulls(&mut out, s.len());
+                }
+                OscArg::Blob(b) => {
+                    out.extend_from_slice(&(b.len() as u32).to_be_bytes());
+                    out.extend_from_slice(b);
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Appends null terminator/padding bytes so that `unpadded_len` rounds up to
+/// a multiple of 4, mirroring [`pad4`].
+fn pad_with_nulls(out: &mut Vec<u8>, unpadded_len: usize) {
+    let padding = pad4(unpadded_len) - unpadded_len;
+    out.resize(out.len() + padding, 0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_vcs_shaped_message() {
+        let mut raw = Vec::new();
+        raw.extend_from_slice(b"/vcs\0\0\0\0");
+        raw.extend_from_slice(b",b\0\0");
+        raw.extend_from_slice(&8u32.to_be_bytes());
+        raw.extend_from_slice(&[0, 0, 0, 42, 0x40, 0x48, 0xf5, 0xc3]);
+
+        let (msg, consumed) = OscMessage::parse(&raw).unwrap();
+        assert_eq!(consumed, raw.len());
+        assert_eq!(msg.address, "/vcs");
+        assert_eq!(
+            msg.args,
+            vec![OscArg::Blob(vec![0, 0, 0, 42, 0x40, 0x48, 0xf5, 0xc3])]
+        );
+    }
+
+    #[test]
+    fn round_trips_mixed_argument_types() {
+        let msg = OscMessage {
+            address: "/synth/1/freq".to_string(),
+            args: vec![
+                OscArg::Int(7),
+                OscArg::Float(440.0),
+                OscArg::String("saw".to_string()),
+                OscArg::Blob(vec![1, 2, 3]),
+            ],
+        };
+        let bytes = msg.to_bytes();
+        let (parsed, consumed) = OscMessage::parse(&bytes).unwrap();
+        assert_eq!(consumed, bytes.len());
+        assert_eq!(parsed, msg);
+    }
+
+    #[test]
+    fn rejects_unknown_type_tag() {
+        let mut raw = Vec::new();
+        raw.extend_from_slice(b"/x\0\0");
+        raw.extend_from_slice(b",z\0\0");
+        assert!(matches!(
+            OscMessage::parse(&raw),
+            Err(OscParseError::UnknownTypeTag { tag: b'z', .. })
+        ));
+    }
+}
@@ -0,0 +1,62 @@
+//! Base64 transport codec, for relays (WebSocket frames, JSON logs, ...)
+//! that can't carry raw OSC bytes.
+
+use crate::error::DecodeError;
+use crate::{from_blob, to_blob, Compression, KymaConcreteEvent};
+use base64::engine::general_purpose::{STANDARD, URL_SAFE};
+use base64::Engine as _;
+
+/// Which base64 alphabet to use for [`from_base64`]/[`to_base64`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Base64Alphabet {
+    /// RFC 4648 standard alphabet, with `+`/`/` and `=` padding.
+    Standard,
+    /// RFC 4648 URL- and filename-safe alphabet, with `-`/`_` and `=` padding.
+    UrlSafe,
+}
+
+/// Decodes a base64-encoded `/vcs` OSC message.
+pub fn from_base64(text: &str, alphabet: Base64Alphabet) -> Result<Vec<KymaConcreteEvent>, DecodeError> {
+    let raw = match alphabet {
+        Base64Alphabet::Standard => STANDARD.decode(text),
+        Base64Alphabet::UrlSafe => URL_SAFE.decode(text),
+    }?;
+    from_blob(&raw)
+}
+
+/// Encodes `events` as a `/vcs` OSC message and base64-encodes the result.
+pub fn to_base64(events: &[KymaConcreteEvent], compression: Compression, alphabet: Base64Alphabet) -> String {
+    let raw = to_blob(events, compression);
+    match alphabet {
+        Base64Alphabet::Standard => STANDARD.encode(raw),
+        Base64Alphabet::UrlSafe => URL_SAFE.encode(raw),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::KymaConcreteEvent;
+
+    #[test]
+    fn round_trips_through_standard_alphabet() {
+        let events = vec![KymaConcreteEvent { event_id: 42, value: 2.5 }];
+        let text = to_base64(&events, Compression::None, Base64Alphabet::Standard);
+        assert_eq!(from_base64(&text, Base64Alphabet::Standard).unwrap(), events);
+    }
+
+    #[test]
+    fn round_trips_through_url_safe_alphabet() {
+        let events = vec![KymaConcreteEvent { event_id: 42, value: 2.5 }];
+        let text = to_base64(&events, Compression::RawDeflate, Base64Alphabet::UrlSafe);
+        assert_eq!(from_base64(&text, Base64Alphabet::UrlSafe).unwrap(), events);
+    }
+
+    #[test]
+    fn rejects_invalid_base64_without_panicking() {
+        assert!(matches!(
+            from_base64("not valid base64!!", Base64Alphabet::Standard),
+            Err(DecodeError::InvalidBase64(_))
+        ));
+    }
+}